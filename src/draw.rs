@@ -0,0 +1,223 @@
+//! Drawing helpers operating directly on [`Color`] frame buffers
+//!
+//! Everything here takes a `&mut [Color]` plus the buffer's `width` (matching the row-major layout
+//! `send_frame` expects), the same convention the bouncing-square example's hand-rolled `draw_rect`
+//! used. This module requires the `draw` feature, since it's the only place this crate depends on
+//! something other than the standard library (`fontdue`, for TrueType rasterization).
+
+use crate::{Color, Error, Result};
+
+fn channels(color: Color) -> [u8; 4] {
+    color.to_be_bytes()
+}
+
+fn color_from_channels(channels: [u8; 4]) -> Color {
+    Color::from_be_bytes(channels)
+}
+
+/// Alpha-composite `src` over `dst`, using `alpha` (0-255) as the blend factor for every channel
+fn blend_pixel(dst: Color, src: Color, alpha: u8) -> Color {
+    let dst = channels(dst);
+    let src = channels(src);
+    let a = alpha as u32;
+
+    let mut out = [0u8; 4];
+    for i in 0..4 {
+        out[i] = ((src[i] as u32 * a + dst[i] as u32 * (255 - a)) / 255) as u8;
+    }
+    color_from_channels(out)
+}
+
+/// Fill a rectangle with a solid, opaque color, overwriting whatever was there before
+///
+/// `(x, y)` is the top-left corner. Pixels that fall outside the buffer are clipped
+pub fn fill_rect(pixels: &mut [Color], width: usize, x: i32, y: i32, w: i32, h: i32, color: Color) {
+    let height = (pixels.len() / width) as i32;
+    for py in y..(y + h) {
+        if !(0..height).contains(&py) { continue; }
+        for px in x..(x + w) {
+            if !(0..width as i32).contains(&px) { continue; }
+            pixels[(py * width as i32 + px) as usize] = color;
+        }
+    }
+}
+
+/// Blend a rectangle of `color` over the existing pixels, honoring `color`'s alpha channel
+///
+/// `(x, y)` is the top-left corner. Pixels that fall outside the buffer are clipped
+pub fn blend_rect(pixels: &mut [Color], width: usize, x: i32, y: i32, w: i32, h: i32, color: Color) {
+    let height = (pixels.len() / width) as i32;
+    let alpha = channels(color)[3];
+    for py in y..(y + h) {
+        if !(0..height).contains(&py) { continue; }
+        for px in x..(x + w) {
+            if !(0..width as i32).contains(&px) { continue; }
+            let idx = (py * width as i32 + px) as usize;
+            pixels[idx] = blend_pixel(pixels[idx], color, alpha);
+        }
+    }
+}
+
+/// Draw a one-pixel-wide line from `(x0, y0)` to `(x1, y1)` using Bresenham's algorithm
+///
+/// Pixels that fall outside the buffer are clipped
+pub fn draw_line(pixels: &mut [Color], width: usize, x0: i32, y0: i32, x1: i32, y1: i32, color: Color) {
+    let height = (pixels.len() / width) as i32;
+
+    let (mut x, mut y) = (x0, y0);
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    loop {
+        if (0..width as i32).contains(&x) && (0..height).contains(&y) {
+            pixels[(y * width as i32 + x) as usize] = color;
+        }
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// A loaded TrueType/OpenType font, used by [`draw_text`]
+///
+/// Load this once and reuse it across frames; rasterizing a font from scratch is not free
+pub struct Font(fontdue::Font);
+
+impl Font {
+    /// Load a font from raw TrueType/OpenType bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Font> {
+        fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())
+            .map(Font)
+            .map_err(|e| Error::FontError(e.to_string()))
+    }
+}
+
+/// Draw `text` with its baseline starting at `origin`, at the given pixel `size`
+///
+/// Glyphs are alpha-composited over `pixels` using their rasterized coverage as the blend factor,
+/// positioned with the font's horizontal advance and kerning. `\n` resets to `origin.0` and advances
+/// the baseline by the font's line height. Glyphs that fall outside the buffer are clipped
+pub fn draw_text(pixels: &mut [Color], width: usize, font: &Font, text: &str, origin: (i32, i32), size: f32, color: Color) {
+    let (x, y) = origin;
+    let height = (pixels.len() / width) as i32;
+    let line_height = font.0.horizontal_line_metrics(size)
+        .map(|metrics| metrics.new_line_size)
+        .unwrap_or(size);
+
+    let mut pen_x = x as f32;
+    let mut pen_y = y as f32;
+    let mut prev_glyph = None;
+
+    for ch in text.chars() {
+        if ch == '\n' {
+            pen_x = x as f32;
+            pen_y += line_height;
+            prev_glyph = None;
+            continue;
+        }
+
+        if let Some(prev) = prev_glyph {
+            pen_x += font.0.horizontal_kern(prev, ch, size).unwrap_or(0.0);
+        }
+
+        let (metrics, coverage) = font.0.rasterize(ch, size);
+
+        // fontdue's bitmap is laid out top-to-bottom, with row 0 at (ymin + height) above the baseline
+        let glyph_x = pen_x.round() as i32 + metrics.xmin;
+        let glyph_y = pen_y.round() as i32 - metrics.ymin - metrics.height as i32;
+
+        for gy in 0..metrics.height {
+            let py = glyph_y + gy as i32;
+            if !(0..height).contains(&py) { continue; }
+            for gx in 0..metrics.width {
+                let px = glyph_x + gx as i32;
+                if !(0..width as i32).contains(&px) { continue; }
+
+                let cov = coverage[gy * metrics.width + gx];
+                if cov == 0 { continue; }
+
+                let idx = (py * width as i32 + px) as usize;
+                pixels[idx] = blend_pixel(pixels[idx], color, cov);
+            }
+        }
+
+        pen_x += metrics.advance_width;
+        prev_glyph = Some(ch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_rect_overwrites_only_the_rect() {
+        let mut pixels = [0u32; 4 * 4];
+        fill_rect(&mut pixels, 4, 1, 1, 2, 2, 0xFF00FF00);
+
+        assert_eq!(pixels[4 + 1], 0xFF00FF00);
+        assert_eq!(pixels[2 * 4 + 2], 0xFF00FF00);
+        assert_eq!(pixels[0], 0);
+        assert_eq!(pixels[3 * 4 + 3], 0);
+    }
+
+    #[test]
+    fn fill_rect_clips_to_the_buffer() {
+        let mut pixels = [0u32; 4 * 4];
+        fill_rect(&mut pixels, 4, 3, 3, 4, 4, 0xFF00FF00);
+
+        assert_eq!(pixels[3 * 4 + 3], 0xFF00FF00);
+    }
+
+    #[test]
+    fn blend_rect_honors_alpha() {
+        let mut pixels = [0xFF0000FFu32; 2 * 2];
+        blend_rect(&mut pixels, 2, 0, 0, 2, 2, 0x00FF0080);
+
+        // ~50% alpha green (0x80) over opaque red: out = src*a/255 + dst*(255-a)/255 per channel
+        let [r, g, b, a] = pixels[0].to_be_bytes();
+        assert_eq!(r, 127);
+        assert_eq!(g, 128);
+        assert_eq!(b, 0);
+        assert_eq!(a, 191);
+    }
+
+    #[test]
+    fn blend_rect_is_a_no_op_at_zero_alpha() {
+        let mut pixels = [0xFF0000FFu32; 1];
+        blend_rect(&mut pixels, 1, 0, 0, 1, 1, 0x00FF0000);
+
+        assert_eq!(pixels[0], 0xFF0000FF);
+    }
+
+    #[test]
+    fn draw_line_draws_endpoints() {
+        let mut pixels = [0u32; 4 * 4];
+        draw_line(&mut pixels, 4, 0, 0, 3, 0, 0xFF00FF00);
+
+        assert_eq!(pixels[0], 0xFF00FF00);
+        assert_eq!(pixels[3], 0xFF00FF00);
+        assert_eq!(pixels[4], 0);
+    }
+
+    #[test]
+    fn draw_line_clips_out_of_bounds_endpoints() {
+        let mut pixels = [0u32; 2 * 2];
+        draw_line(&mut pixels, 2, -5, 0, 1, 0, 0xFF00FF00);
+
+        assert_eq!(pixels[0], 0xFF00FF00);
+        assert_eq!(pixels[1], 0xFF00FF00);
+    }
+}
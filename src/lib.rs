@@ -8,7 +8,7 @@
 //! as a module.
 //!
 //! ## Basic Usage
-//! ```rust
+//! ```rust,ignore
 //! use simple_ffmpeg as ffmpeg;
 //!
 //! let mut ffmpeg = ffmpeg::start("out.mp4", WIDTH, HEIGHT, FPS)?;
@@ -22,13 +22,26 @@
 //!
 //! ffmpeg.finalize()?;
 //! ```
+//!
+//! ## Drawing
+//! Enable the `draw` feature for [`draw::fill_rect`], [`draw::blend_rect`], [`draw::draw_line`] and
+//! TrueType text rendering via [`draw::draw_text`], so you don't have to hand-roll pixel blitting.
+//! This is the only part of the crate with a dependency (`fontdue`), which is why it's opt-in.
+
+#[cfg(feature = "draw")]
+pub mod draw;
 
 use std::error;
 use std::result;
 use std::fmt;
 use std::process::{Command, Child, Stdio, ExitStatus};
-use std::io::Write;
+use std::io::{Write, BufRead, BufReader};
 use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::collections::VecDeque;
 
 /// Representation of a single pixel
 ///
@@ -44,6 +57,260 @@ pub fn get_color(r: u8, g: u8, b: u8, a: u8) -> Color {
     Color::from_be_bytes([r, g, b, a])
 }
 
+/// Video/audio codec pair to encode with
+///
+/// Picked via [`FFMpegBuilder::codec`]. If you don't pick one yourself, [`FFMpeg::start`] picks
+/// [`Codec::Av1Opus`] at 1440p and above, and [`Codec::AvcAac`] below that
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// H.264 video + AAC audio, decodes everywhere but compresses worse than AV1
+    AvcAac,
+    /// AV1 video + Opus audio, smaller files at the same quality but slower to encode
+    Av1Opus,
+}
+
+impl Codec {
+    /// Pick the default codec for a given resolution
+    ///
+    /// AV1/Opus from 1440p upwards (keyed off the short edge, so orientation doesn't matter),
+    /// H.264/AAC below that
+    pub fn default_for_resolution(width: usize, height: usize) -> Codec {
+        if width.min(height) >= 1440 {
+            Codec::Av1Opus
+        } else {
+            Codec::AvcAac
+        }
+    }
+
+    /// The `-c:a` encoder this codec pairs its video encoder with
+    fn audio_codec_name(&self) -> &'static str {
+        match self {
+            Codec::AvcAac => "aac",
+            Codec::Av1Opus => "libopus",
+        }
+    }
+
+    fn ffmpeg_args(&self, bitrate: &str) -> Vec<String> {
+        let video_codec_name = match self {
+            Codec::AvcAac => "libx264",
+            Codec::Av1Opus => "libsvtav1",
+        };
+        vec![
+            "-c:v".to_string(), video_codec_name.to_string(),
+            "-b:v".to_string(), bitrate.to_string(),
+            "-c:a".to_string(), self.audio_codec_name().to_string(),
+        ]
+    }
+}
+
+/// Pixel format to feed ffmpeg with
+///
+/// Picked via [`FFMpegBuilder::pixel_format`]. Defaults to [`PixelFormat::Rgba`], which is what
+/// [`FFMpeg::send_frame`] expects. Any other format must be sent with [`FFMpeg::send_frame_bytes`]
+/// instead, since those formats don't pack into a whole number of [`Color`]s
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 32-bit RGBA, 4 bytes per pixel. The default, and the only format [`FFMpeg::send_frame`] accepts
+    Rgba,
+    /// 24-bit RGB, 3 bytes per pixel, no alpha channel
+    Rgb24,
+    /// 8-bit grayscale, 1 byte per pixel
+    Gray8,
+    /// Planar YUV 4:2:0, 12 bits per pixel on average (full-res luma plane, quarter-res chroma planes)
+    Yuv420p,
+}
+
+impl PixelFormat {
+    /// The `-pix_fmt` value ffmpeg expects for this format
+    fn ffmpeg_name(&self) -> &'static str {
+        match self {
+            PixelFormat::Rgba => "rgba",
+            PixelFormat::Rgb24 => "rgb24",
+            PixelFormat::Gray8 => "gray",
+            PixelFormat::Yuv420p => "yuv420p",
+        }
+    }
+
+    /// How many bytes a single frame of this format takes up at the given resolution
+    pub fn bytes_per_frame(&self, width: usize, height: usize) -> usize {
+        match self {
+            PixelFormat::Rgba => width * height * 4,
+            PixelFormat::Rgb24 => width * height * 3,
+            PixelFormat::Gray8 => width * height,
+            PixelFormat::Yuv420p => width * height + 2 * width.div_ceil(2) * height.div_ceil(2),
+        }
+    }
+}
+
+/// ffmpeg's `-loglevel`
+///
+/// Picked via [`FFMpegBuilder::log_level`]. Defaults to [`LogLevel::Verbose`], matching the
+/// previous hardcoded behavior. Raise this to [`LogLevel::Debug`] when diagnosing a failing render
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    /// No output at all
+    Quiet,
+    /// Only errors
+    Error,
+    /// Errors and warnings
+    Warning,
+    /// Errors, warnings, and informational messages
+    Info,
+    /// Informational messages plus more detail about what ffmpeg is doing
+    Verbose,
+    /// Everything, including internal debugging information
+    Debug,
+}
+
+impl LogLevel {
+    fn ffmpeg_name(&self) -> &'static str {
+        match self {
+            LogLevel::Quiet => "quiet",
+            LogLevel::Error => "error",
+            LogLevel::Warning => "warning",
+            LogLevel::Info => "info",
+            LogLevel::Verbose => "verbose",
+            LogLevel::Debug => "debug",
+        }
+    }
+}
+
+/// Number of trailing stderr lines kept around to attach to [`Error::FFMpegExitedAbnormally`]
+const STDERR_TAIL_LINES: usize = 50;
+
+/// Drain ffmpeg's stderr on a background thread, keeping the last [`STDERR_TAIL_LINES`] lines around
+///
+/// The returned [`thread::JoinHandle`] must be joined (after the child's stdout/stderr pipes are
+/// closed, e.g. by `child.wait()`) before the tail is read, or the last lines — typically the
+/// fatal error a fast-failing ffmpeg printed — may not have been drained yet
+fn spawn_stderr_reader(pipe: impl std::io::Read + Send + 'static) -> (Arc<Mutex<VecDeque<String>>>, thread::JoinHandle<()>) {
+    let tail = Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES)));
+
+    let tail_writer = Arc::clone(&tail);
+    let handle = thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else { break };
+            let mut tail = tail_writer.lock().unwrap_or_else(|e| e.into_inner());
+            if tail.len() == STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+    });
+
+    (tail, handle)
+}
+
+/// Hardware acceleration to encode with
+///
+/// Picked via [`FFMpegBuilder::hwaccel`]. Defaults to [`HwAccel::None`] (software encoding)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum HwAccel {
+    /// Software encoding, using whatever [`Codec`] was picked
+    #[default]
+    None,
+    /// VAAPI hardware-accelerated encoding
+    ///
+    /// Only takes effect when this crate is built with the `vaapi` feature; without it, this
+    /// variant is a no-op and encoding falls back to the software path
+    Vaapi {
+        /// DRM render node to use, e.g. `/dev/dri/renderD128`. Defaults to `/dev/dri/renderD128` when `None`
+        device: Option<PathBuf>,
+    },
+}
+
+impl HwAccel {
+    /// Global ffmpeg args that must appear before `-i`, e.g. `-vaapi_device`
+    fn global_args(&self) -> Vec<String> {
+        #[cfg(feature = "vaapi")]
+        if let HwAccel::Vaapi { device } = self {
+            let device = device.clone().unwrap_or_else(|| PathBuf::from("/dev/dri/renderD128"));
+            return vec!["-vaapi_device".to_string(), device.display().to_string()];
+        }
+        Vec::new()
+    }
+
+    /// Output-side ffmpeg args, replacing [`Codec::ffmpeg_args`] when hardware encoding is active
+    ///
+    /// VAAPI only has a hardware H.264 video encoder, so it always overrides `-c:v`, but it still
+    /// honors the selected [`Codec`]'s audio encoder rather than forcing one
+    fn output_args(&self, codec: Codec, bitrate: &str) -> Vec<String> {
+        #[cfg(feature = "vaapi")]
+        if let HwAccel::Vaapi { .. } = self {
+            return vec![
+                "-vf".to_string(), "format=nv12,hwupload".to_string(),
+                "-c:v".to_string(), "h264_vaapi".to_string(),
+                "-b:v".to_string(), bitrate.to_string(),
+                "-c:a".to_string(), codec.audio_codec_name().to_string(),
+            ];
+        }
+        codec.ffmpeg_args(bitrate)
+    }
+}
+
+/// Where to pull an audio track from to mux into the rendered video
+///
+/// Picked via [`FFMpegBuilder::audio`]. Without one, the output video is silent, as before
+#[derive(Debug, Clone)]
+pub enum AudioSource {
+    /// Mux in an existing audio file (e.g. a WAV or MP3) via a second ffmpeg input
+    File(PathBuf),
+    /// Mux in raw, interleaved 16-bit PCM samples pushed at render time via [`FFMpeg::send_audio`]
+    Piped {
+        /// Sample rate in Hz, e.g. `44100`
+        sample_rate: u32,
+        /// Number of interleaved channels, e.g. `2` for stereo
+        channels: u16,
+    },
+}
+
+impl AudioSource {
+    /// ffmpeg args for the second `-i`. `fifo_path` must be `Some` for [`AudioSource::Piped`]
+    fn input_args(&self, fifo_path: Option<&std::path::Path>) -> Vec<std::ffi::OsString> {
+        match self {
+            AudioSource::File(path) => vec!["-i".into(), path.clone().into_os_string()],
+            AudioSource::Piped { sample_rate, channels } => {
+                let fifo_path = fifo_path.expect("piped audio source needs a fifo path");
+                vec![
+                    "-f".into(), "s16le".into(),
+                    "-ar".into(), sample_rate.to_string().into(),
+                    "-ac".into(), channels.to_string().into(),
+                    "-i".into(), fifo_path.as_os_str().to_os_string(),
+                ]
+            }
+        }
+    }
+}
+
+/// Create a FIFO at a fresh path in the system temp directory for piped audio input
+fn make_audio_fifo() -> Result<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let path = std::env::temp_dir().join(format!("simple-ffmpeg-audio-{}-{nanos}.pcm", std::process::id()));
+
+    let status = Command::new("mkfifo").arg(&path).status()?;
+    if !status.success() {
+        return Err(Error::IOError(std::io::Error::other("mkfifo failed to create the audio pipe")));
+    }
+
+    Ok(path)
+}
+
+/// Pick a default video bitrate for a resolution
+///
+/// Keyed off the long edge of the frame, using the standard nHD/HD/FullHD/WQHD/UHD buckets:
+/// nHD≈500k, HD≈1M, FullHD≈2M, WQHD≈3M, UHD≈4M
+pub fn default_bitrate(width: usize, height: usize) -> &'static str {
+    match width.max(height) {
+        0..=640 => "500k",
+        641..=1280 => "1M",
+        1281..=1920 => "2M",
+        1921..=2560 => "3M",
+        _ => "4M",
+    }
+}
+
 /// Main error type
 ///
 /// This error is returned from every function in this crate that can fail (which is most of them)
@@ -52,18 +319,34 @@ pub enum Error {
     /// IO Error
     IOError(std::io::Error),
     /// FFMpeg exited with non-zero code
-    FFMpegExitedAbnormally(ExitStatus),
+    FFMpegExitedAbnormally {
+        /// The exit status ffmpeg returned
+        status: ExitStatus,
+        /// The last [`STDERR_TAIL_LINES`] lines ffmpeg wrote to stderr before exiting
+        stderr: String,
+    },
+    /// Failed to parse a font (only returned by [`draw::Font::from_bytes`])
+    #[cfg(feature = "draw")]
+    FontError(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Error::FFMpegExitedAbnormally(code) => if let Some(code) = code.code() {
-                write!(f, "ffmpeg exited abnormally with code {code}")
-            } else {
-                write!(f, "ffmpeg exited abnormally")
-            },
+            Error::FFMpegExitedAbnormally { status, stderr } => {
+                if let Some(code) = status.code() {
+                    write!(f, "ffmpeg exited abnormally with code {code}")?;
+                } else {
+                    write!(f, "ffmpeg exited abnormally")?;
+                }
+                if !stderr.is_empty() {
+                    write!(f, ":\n{stderr}")?;
+                }
+                Ok(())
+            }
             Error::IOError(e) => write!(f, "io error: {e}"),
+            #[cfg(feature = "draw")]
+            Error::FontError(e) => write!(f, "failed to parse font: {e}"),
         }
     }
 }
@@ -79,6 +362,95 @@ impl From<std::io::Error> for Error {
 /// This Result is returned from every function in this crate that can fail (which is most of them)
 pub type Result<T> = result::Result<T, Error>;
 
+/// A single progress update parsed from ffmpeg's `-progress` machine-readable output
+///
+/// Drained via [`FFMpeg::on_progress`]. Any field ffmpeg didn't report in a given update is `None`
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    /// Number of frames encoded so far
+    pub frame: Option<u64>,
+    /// Current encoding speed in frames per second
+    pub fps: Option<f64>,
+    /// Position in the output in microseconds
+    pub out_time_us: Option<u64>,
+    /// Size of the output so far, in bytes
+    pub total_size: Option<u64>,
+    /// Encoding speed relative to realtime (e.g. `2.5` means 2.5x realtime)
+    pub speed: Option<f64>,
+}
+
+impl Progress {
+    fn apply_kv(&mut self, key: &str, value: &str) {
+        match key {
+            "frame" => self.frame = value.parse().ok(),
+            "fps" => self.fps = value.parse().ok(),
+            "out_time_us" => self.out_time_us = value.parse().ok(),
+            "total_size" => self.total_size = value.parse().ok(),
+            "speed" => self.speed = value.trim_end_matches('x').trim().parse().ok(),
+            _ => {}
+        }
+    }
+}
+
+/// Parse ffmpeg's `-progress` output into [`Progress`] updates, one per `progress=continue`/`progress=end` line
+fn spawn_progress_reader(pipe: impl std::io::Read + Send + 'static) -> mpsc::Receiver<Progress> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut progress = Progress::default();
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else { break };
+            let Some((key, value)) = line.split_once('=') else { continue };
+            let (key, value) = (key.trim(), value.trim());
+
+            if key == "progress" {
+                let finished = progress.clone();
+                progress = Progress::default();
+                if tx.send(finished).is_err() {
+                    break;
+                }
+                if value == "end" {
+                    break;
+                }
+            } else {
+                progress.apply_kv(key, value);
+            }
+        }
+    });
+
+    rx
+}
+
+/// A single frame to hold for an intro or outro, passed to [`FFMpeg::send_intro`]/[`FFMpeg::send_outro`]
+///
+/// Converts from both a [`Color`] (filling the whole frame) and a `&[Color]` (an explicit frame buffer)
+pub enum HoldFrame<'a> {
+    /// Fill the whole frame with a solid color
+    Color(Color),
+    /// Hold this caller-supplied frame verbatim
+    Frame(&'a [Color]),
+}
+
+impl From<Color> for HoldFrame<'_> {
+    fn from(color: Color) -> Self { HoldFrame::Color(color) }
+}
+
+impl<'a> From<&'a [Color]> for HoldFrame<'a> {
+    fn from(frame: &'a [Color]) -> Self { HoldFrame::Frame(frame) }
+}
+
+/// Blend two colors channel-by-channel, `out = a*(1-t) + b*t`
+fn blend_colors(a: Color, b: Color, t: f32) -> Color {
+    let a = a.to_be_bytes();
+    let b = b.to_be_bytes();
+
+    let mut out = [0u8; 4];
+    for i in 0..4 {
+        out[i] = (a[i] as f32 * (1.0 - t) + b[i] as f32 * t).round() as u8;
+    }
+    Color::from_be_bytes(out)
+}
+
 /// Main interface into FFMPEG
 ///
 /// This struct holds a child ffmpeg process that you can send frames into. Remember to call [`FFMpeg::finalize`] when you're done.
@@ -88,6 +460,12 @@ pub struct FFMpeg {
     width: usize,
     height: usize,
     fps: u32,
+    pixel_format: PixelFormat,
+    audio_pipe: Option<std::fs::File>,
+    audio_fifo_path: Option<PathBuf>,
+    progress_rx: mpsc::Receiver<Progress>,
+    stderr_tail: Arc<Mutex<VecDeque<String>>>,
+    stderr_reader: Option<thread::JoinHandle<()>>,
 }
 
 /// Start the FFMPEG rendering
@@ -97,25 +475,161 @@ pub fn start(out_file: impl AsRef<OsStr>, width: usize, height: usize, fps: u32)
     FFMpeg::start(out_file, width, height, fps)
 }
 
-impl FFMpeg {
-    /// Start the FFMPEG rendering
+/// Builder for [`FFMpeg`], letting you pick a codec and bitrate before starting the render
+///
+/// Construct one with [`FFMpeg::builder`]
+pub struct FFMpegBuilder {
+    out_file: std::ffi::OsString,
+    width: usize,
+    height: usize,
+    fps: u32,
+    codec: Option<Codec>,
+    bitrate: Option<String>,
+    hwaccel: HwAccel,
+    pixel_format: PixelFormat,
+    audio: Option<AudioSource>,
+    log_level: LogLevel,
+}
+
+impl FFMpegBuilder {
+    /// Pick the codec to encode with
     ///
-    /// Starts the FFMPEG rendering.
-    pub fn start(out_file: impl AsRef<OsStr>, width: usize, height: usize, fps: u32) -> Result<FFMpeg> {
-        let child = Command::new("ffmpeg")
-            .args(["-loglevel", "verbose", "-y"])
+    /// If you don't call this, the codec is picked based on resolution, see [`Codec::default_for_resolution`]
+    pub fn codec(mut self, codec: Codec) -> FFMpegBuilder {
+        self.codec = Some(codec);
+        self
+    }
+
+    /// Pick the video bitrate to encode with (e.g. `"2M"`, `"500k"`)
+    ///
+    /// If you don't call this, the bitrate is picked based on resolution, see [`default_bitrate`]
+    pub fn bitrate(mut self, bitrate: impl Into<String>) -> FFMpegBuilder {
+        self.bitrate = Some(bitrate.into());
+        self
+    }
+
+    /// Pick hardware acceleration to encode with
+    ///
+    /// Defaults to [`HwAccel::None`] (software encoding). [`HwAccel::Vaapi`] only takes effect
+    /// when this crate is built with the `vaapi` feature
+    pub fn hwaccel(mut self, hwaccel: HwAccel) -> FFMpegBuilder {
+        self.hwaccel = hwaccel;
+        self
+    }
+
+    /// Pick the pixel format frames are fed in as
+    ///
+    /// Defaults to [`PixelFormat::Rgba`]. Picking a smaller format (e.g. [`PixelFormat::Yuv420p`])
+    /// cuts down on the bytes written per frame, at the cost of having to feed frames through
+    /// [`FFMpeg::send_frame_bytes`] instead of [`FFMpeg::send_frame`]
+    pub fn pixel_format(mut self, pixel_format: PixelFormat) -> FFMpegBuilder {
+        self.pixel_format = pixel_format;
+        self
+    }
+
+    /// Mux an audio track into the output video
+    ///
+    /// Without this, the output video is silent, as before
+    pub fn audio(mut self, audio: AudioSource) -> FFMpegBuilder {
+        self.audio = Some(audio);
+        self
+    }
+
+    /// Pick ffmpeg's `-loglevel`
+    ///
+    /// Defaults to [`LogLevel::Verbose`]. Raise this to [`LogLevel::Debug`] when diagnosing a failing render
+    pub fn log_level(mut self, log_level: LogLevel) -> FFMpegBuilder {
+        self.log_level = log_level;
+        self
+    }
+
+    /// Start the FFMPEG rendering with the options configured on this builder
+    pub fn start(self) -> Result<FFMpeg> {
+        let codec = self.codec.unwrap_or_else(|| Codec::default_for_resolution(self.width, self.height));
+        let bitrate = self.bitrate.unwrap_or_else(|| default_bitrate(self.width, self.height).to_string());
+
+        let audio_fifo_path = match &self.audio {
+            Some(AudioSource::Piped { .. }) => Some(make_audio_fifo()?),
+            _ => None,
+        };
+
+        let mut cmd = Command::new("ffmpeg");
+        cmd.args(["-loglevel", self.log_level.ffmpeg_name(), "-y"])
+            .args(["-progress", "pipe:1", "-nostats"])
+            .args(self.hwaccel.global_args())
             // Input file options
             .args(["-f", "rawvideo"])
-            .args(["-pix_fmt", "rgba"])
-            .args(["-s", &format!("{width}x{height}")])
-            .args(["-r", &format!("{fps}")])
-            .args(["-i", "-"])
-            // Output file options
-            .arg(out_file)
+            .args(["-pix_fmt", self.pixel_format.ffmpeg_name()])
+            .args(["-s", &format!("{}x{}", self.width, self.height)])
+            .args(["-r", &format!("{}", self.fps)])
+            .args(["-i", "-"]);
+
+        if let Some(audio) = &self.audio {
+            cmd.args(audio.input_args(audio_fifo_path.as_deref()));
+        }
+
+        // Output file options
+        cmd.args(self.hwaccel.output_args(codec, &bitrate));
+        if self.audio.is_some() {
+            cmd.arg("-shortest");
+        }
+        cmd.arg(&self.out_file)
             .stdin(Stdio::piped())
-            .spawn()?;
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = cmd.spawn()?;
+        let progress_rx = spawn_progress_reader(child.stdout.take().expect("we set stdout to piped"));
+        let (stderr_tail, stderr_reader) = spawn_stderr_reader(child.stderr.take().expect("we set stderr to piped"));
 
-        Ok(FFMpeg { child, width, height, fps })
+        let audio_pipe = match (&self.audio, &audio_fifo_path) {
+            (Some(AudioSource::Piped { .. }), Some(fifo_path)) => {
+                Some(std::fs::OpenOptions::new().write(true).open(fifo_path)?)
+            }
+            _ => None,
+        };
+
+        Ok(FFMpeg {
+            child,
+            width: self.width,
+            height: self.height,
+            fps: self.fps,
+            pixel_format: self.pixel_format,
+            audio_pipe,
+            audio_fifo_path,
+            progress_rx,
+            stderr_tail,
+            stderr_reader: Some(stderr_reader),
+        })
+    }
+}
+
+impl FFMpeg {
+    /// Start building an FFMPEG render with a custom codec and/or bitrate
+    ///
+    /// Call [`FFMpegBuilder::start`] once you've configured it
+    pub fn builder(out_file: impl AsRef<OsStr>, width: usize, height: usize, fps: u32) -> FFMpegBuilder {
+        FFMpegBuilder {
+            out_file: out_file.as_ref().to_os_string(),
+            width,
+            height,
+            fps,
+            codec: None,
+            bitrate: None,
+            hwaccel: HwAccel::default(),
+            pixel_format: PixelFormat::Rgba,
+            audio: None,
+            log_level: LogLevel::Verbose,
+        }
+    }
+
+    /// Start the FFMPEG rendering
+    ///
+    /// Starts the FFMPEG rendering with the default codec and bitrate for the given resolution,
+    /// see [`Codec::default_for_resolution`] and [`default_bitrate`]. Use [`FFMpeg::builder`] if
+    /// you want to pick these yourself.
+    pub fn start(out_file: impl AsRef<OsStr>, width: usize, height: usize, fps: u32) -> Result<FFMpeg> {
+        FFMpeg::builder(out_file, width, height, fps).start()
     }
 
     /// Get the render width
@@ -130,14 +644,29 @@ impl FFMpeg {
     /// Get the render resolution
     pub fn resolution(&self) -> (usize, usize) { (self.width, self.height) }
 
+    /// Get the pixel format frames are expected in
+    pub fn pixel_format(&self) -> PixelFormat { self.pixel_format }
+
+    /// Drain any [`Progress`] updates ffmpeg has reported since the last call, invoking `callback` for each
+    ///
+    /// Call this periodically (e.g. once per [`FFMpeg::send_frame`]) to get a percentage or ETA out for a UI or CLI
+    pub fn on_progress(&mut self, mut callback: impl FnMut(Progress)) {
+        while let Ok(progress) = self.progress_rx.try_recv() {
+            callback(progress);
+        }
+    }
+
     /// Send a frame to the FFMPEG process
     ///
-    /// Send a frame to the FFMPEG process. `pixels.len()` must be equal to `ffmpeg.width() * ffmpeg.height()`
+    /// Send a frame to the FFMPEG process. `pixels.len()` must be equal to `ffmpeg.width() * ffmpeg.height()`.
+    /// Only valid when the pixel format is [`PixelFormat::Rgba`] (the default); for any other
+    /// pixel format use [`FFMpeg::send_frame_bytes`] instead
+    ///
+    /// If you're also calling [`FFMpeg::send_audio`], see its docs for a pipe-interleaving caveat
     pub fn send_frame(&mut self, pixels: &[Color]) -> Result<()> {
+        assert_eq!(self.pixel_format, PixelFormat::Rgba, "send_frame only supports PixelFormat::Rgba, use send_frame_bytes for other pixel formats");
         assert_eq!(pixels.len(), self.width * self.height);
 
-        let stdin = self.child.stdin.as_mut().expect("we set stdin to piped");
-
         let pixels_u8: &[u8] = unsafe {
             let ptr = pixels.as_ptr();
             let len = pixels.len();
@@ -145,8 +674,93 @@ impl FFMpeg {
             use std::mem::size_of;
             std::slice::from_raw_parts(ptr as *const u8, len * (size_of::<Color>() / size_of::<u8>()))
         };
-        stdin.write_all(pixels_u8)?;
+        self.send_frame_bytes(pixels_u8)
+    }
+
+    /// Send a raw frame to the FFMPEG process
+    ///
+    /// Unlike [`FFMpeg::send_frame`], this works for any [`PixelFormat`]. `bytes.len()` must be equal
+    /// to `ffmpeg.pixel_format().bytes_per_frame(ffmpeg.width(), ffmpeg.height())`
+    pub fn send_frame_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        assert_eq!(bytes.len(), self.pixel_format.bytes_per_frame(self.width, self.height));
+
+        let stdin = self.child.stdin.as_mut().expect("we set stdin to piped");
+        stdin.write_all(bytes)?;
+
+        Ok(())
+    }
+
+    /// Send raw, interleaved 16-bit PCM audio samples to the FFMPEG process
+    ///
+    /// Only valid when the builder was configured with [`AudioSource::Piped`]
+    ///
+    /// This writes straight to the audio fifo from the calling thread, same as [`FFMpeg::send_frame`]
+    /// writes straight to stdin. ffmpeg only drains one of the two pipes at a time, so pushing a large
+    /// batch of audio in one call (instead of small chunks interleaved with frames) can fill the
+    /// kernel pipe buffer while ffmpeg is blocked writing out video, deadlocking both sides. Call this
+    /// with chunks sized to roughly one frame's worth of audio, alternating with [`FFMpeg::send_frame`]
+    pub fn send_audio(&mut self, samples: &[i16]) -> Result<()> {
+        let pipe = self.audio_pipe.as_mut().expect("send_audio requires the builder to be configured with AudioSource::Piped");
+
+        let samples_u8: &[u8] = unsafe {
+            let ptr = samples.as_ptr();
+            let len = samples.len();
+
+            use std::mem::size_of;
+            std::slice::from_raw_parts(ptr as *const u8, len * (size_of::<i16>() / size_of::<u8>()))
+        };
+        pipe.write_all(samples_u8)?;
+
+        Ok(())
+    }
+
+    /// Hold a frame for an intro, sending it `frames` times before the rest of the render
+    ///
+    /// `frame` can be a solid [`Color`] (filling the whole frame) or a caller-supplied `&[Color]`
+    pub fn send_intro<'a>(&mut self, frame: impl Into<HoldFrame<'a>>, frames: usize) -> Result<()> {
+        self.send_held_frame(frame.into(), frames)
+    }
+
+    /// Hold a frame for an outro, sending it `frames` times after the rest of the render
+    ///
+    /// `frame` can be a solid [`Color`] (filling the whole frame) or a caller-supplied `&[Color]`
+    pub fn send_outro<'a>(&mut self, frame: impl Into<HoldFrame<'a>>, frames: usize) -> Result<()> {
+        self.send_held_frame(frame.into(), frames)
+    }
 
+    fn send_held_frame(&mut self, frame: HoldFrame, frames: usize) -> Result<()> {
+        match frame {
+            HoldFrame::Color(color) => {
+                let buf = vec![color; self.width * self.height];
+                for _ in 0..frames {
+                    self.send_frame(&buf)?;
+                }
+            }
+            HoldFrame::Frame(buf) => {
+                for _ in 0..frames {
+                    self.send_frame(buf)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Crossfade from `a` to `b` over `frames` frames, sending each blended frame via [`FFMpeg::send_frame`]
+    ///
+    /// Blends per pixel as `out = a*(1-t) + b*t` with `t = i/(frames-1)`. Pick `frames` to match
+    /// the transition length you want, e.g. ~0.2s worth of frames for a short cut between clips
+    pub fn crossfade(&mut self, a: &[Color], b: &[Color], frames: usize) -> Result<()> {
+        assert_eq!(a.len(), self.width * self.height);
+        assert_eq!(b.len(), self.width * self.height);
+
+        let mut blended = vec![0 as Color; a.len()];
+        for i in 0..frames {
+            let t = if frames <= 1 { 1.0 } else { i as f32 / (frames - 1) as f32 };
+            for (out, (&pa, &pb)) in blended.iter_mut().zip(a.iter().zip(b.iter())) {
+                *out = blend_colors(pa, pb, t);
+            }
+            self.send_frame(&blended)?;
+        }
         Ok(())
     }
 
@@ -155,9 +769,18 @@ impl FFMpeg {
     /// If this method isn't called directly or indirectly (such as if `std::mem::forget` is called on `FFMpeg`),
     /// the final video may not be complete
     pub fn finalize(mut self) -> Result<()> {
+        self.audio_pipe.take();
         let retcode = self.child.wait()?;
+        if let Some(handle) = self.stderr_reader.take() {
+            _ = handle.join();
+        }
+        if let Some(fifo_path) = self.audio_fifo_path.take() {
+            _ = std::fs::remove_file(fifo_path);
+        }
         if !retcode.success() {
-            return Err(Error::FFMpegExitedAbnormally(retcode));
+            let stderr = self.stderr_tail.lock().unwrap_or_else(|e| e.into_inner())
+                .iter().cloned().collect::<Vec<_>>().join("\n");
+            return Err(Error::FFMpegExitedAbnormally { status: retcode, stderr });
         }
         Ok(())
     }
@@ -165,6 +788,59 @@ impl FFMpeg {
 
 impl std::ops::Drop for FFMpeg {
     fn drop(&mut self) {
+        self.audio_pipe.take();
         _ = self.child.wait();
+        if let Some(handle) = self.stderr_reader.take() {
+            _ = handle.join();
+        }
+        if let Some(fifo_path) = self.audio_fifo_path.take() {
+            _ = std::fs::remove_file(fifo_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bitrate_matches_named_resolutions() {
+        assert_eq!(default_bitrate(640, 360), "500k");
+        assert_eq!(default_bitrate(1280, 720), "1M");
+        assert_eq!(default_bitrate(1920, 1080), "2M");
+        assert_eq!(default_bitrate(2560, 1440), "3M");
+        assert_eq!(default_bitrate(3840, 2160), "4M");
+    }
+
+    #[test]
+    fn default_bitrate_picks_up_one_step_past_each_edge() {
+        assert_eq!(default_bitrate(641, 360), "1M");
+        assert_eq!(default_bitrate(1281, 720), "2M");
+        assert_eq!(default_bitrate(1921, 1080), "3M");
+        assert_eq!(default_bitrate(2561, 1440), "4M");
+    }
+
+    #[test]
+    fn default_for_resolution_keys_off_the_short_edge() {
+        // 1080p: long edge (1920) is past 1440, but the short edge isn't, so stay on AvcAac
+        assert_eq!(Codec::default_for_resolution(1920, 1080), Codec::AvcAac);
+        // 1440p and up (in either orientation) switches to Av1Opus
+        assert_eq!(Codec::default_for_resolution(2560, 1440), Codec::Av1Opus);
+        assert_eq!(Codec::default_for_resolution(1440, 2560), Codec::Av1Opus);
+    }
+
+    #[test]
+    fn blend_colors_interpolates_each_channel() {
+        assert_eq!(blend_colors(0x00000000, 0xFFFFFFFF, 0.0), 0x00000000);
+        assert_eq!(blend_colors(0x00000000, 0xFFFFFFFF, 1.0), 0xFFFFFFFF);
+        assert_eq!(blend_colors(0x00000000, 0xFFFFFFFF, 0.5), 0x80808080);
+    }
+
+    #[test]
+    fn bytes_per_frame_accounts_for_chroma_subsampling() {
+        assert_eq!(PixelFormat::Rgba.bytes_per_frame(4, 4), 64);
+        assert_eq!(PixelFormat::Yuv420p.bytes_per_frame(4, 4), 24);
+        // odd dimensions round the chroma planes up
+        assert_eq!(PixelFormat::Yuv420p.bytes_per_frame(3, 3), 9 + 2 * 2 * 2);
     }
 }